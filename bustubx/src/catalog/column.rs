@@ -2,14 +2,26 @@ use sqlparser::ast::ColumnDef;
 use std::sync::Arc;
 
 use crate::catalog::DataType;
+use crate::common::ScalarValue;
+use crate::primer::hyperloglog::HyperLogLog;
+use crate::primer::top_k::TopK;
 
 pub type ColumnRef = Arc<Column>;
 
+// the default register width used when sketching a column's distinct values
+const DEFAULT_HLL_BITS: i16 = 12;
+// the default Count-Min Sketch error bounds backing each column's TopK
+const DEFAULT_MCV_EPSILON: f64 = 0.01;
+const DEFAULT_MCV_DELTA: f64 = 0.01;
+// how many most-common-values entries to keep per column by default
+const DEFAULT_MCV_LIMIT: usize = 10;
+
 // 列定义
 #[derive(Debug, Clone)]
 pub struct Column {
     pub name: String,
     pub data_type: DataType,
+    pub statistics: Option<ColumnStatistics>,
 }
 
 impl PartialEq for Column {
@@ -20,7 +32,11 @@ impl PartialEq for Column {
 
 impl Column {
     pub fn new(name: String, data_type: DataType) -> Self {
-        Self { name, data_type }
+        Self {
+            name,
+            data_type,
+            statistics: None,
+        }
     }
 
     pub fn from_sqlparser_column(column_def: &ColumnDef) -> Self {
@@ -29,3 +45,84 @@ impl Column {
         Self::new(column_name, column_type)
     }
 }
+
+/// Per-column statistics populated by scanning (or sampling) a table, used by the
+/// optimizer to derive selectivity and cardinality estimates for `LogicalPlanV2`.
+#[derive(Debug, Clone, Default)]
+pub struct ColumnStatistics {
+    pub row_count: u64,
+    pub distinct_count: u64,
+    pub null_count: u64,
+    pub min: Option<ScalarValue>,
+    pub max: Option<ScalarValue>,
+    pub most_common_values: Vec<(ScalarValue, u64)>,
+}
+
+impl ColumnStatistics {
+    /// Builds statistics from a single pass over a column's values: distinct count via
+    /// `HyperLogLog`, a most-common-values list via `TopK` (backed by a
+    /// `CountMinSketch`), and exact row/null/min/max bookkeeping. `row_count` is kept
+    /// alongside the most-common-values counts so `equality_selectivity` always
+    /// divides by the population those counts were taken over, not by whatever row
+    /// count happens to be in scope when it's later called.
+    pub fn build<'a>(values: impl Iterator<Item = &'a ScalarValue>) -> Self {
+        let mut hll: HyperLogLog<ScalarValue> = HyperLogLog::new(DEFAULT_HLL_BITS);
+        let mut top_k: TopK<ScalarValue> =
+            TopK::new(DEFAULT_MCV_LIMIT, DEFAULT_MCV_EPSILON, DEFAULT_MCV_DELTA);
+        let mut row_count = 0u64;
+        let mut null_count = 0u64;
+        let mut min: Option<ScalarValue> = None;
+        let mut max: Option<ScalarValue> = None;
+
+        for value in values {
+            row_count += 1;
+
+            if value.is_null() {
+                null_count += 1;
+                continue;
+            }
+
+            hll.add_elem(value.clone());
+            top_k.offer(value.clone());
+
+            min = Some(match min {
+                Some(current) if current <= *value => current,
+                _ => value.clone(),
+            });
+            max = Some(match max {
+                Some(current) if current >= *value => current,
+                _ => value.clone(),
+            });
+        }
+
+        hll.compute_cardinality();
+        Self {
+            row_count,
+            distinct_count: hll.get_cardinality() as u64,
+            null_count,
+            min,
+            max,
+            most_common_values: top_k.top_k(),
+        }
+    }
+
+    /// The equality-predicate selectivity for `value`: its most-common-value frequency
+    /// (over `self.row_count`, the population these statistics were built from) if
+    /// tracked, else a uniform `1 / distinct_count` assumption.
+    pub fn equality_selectivity(&self, value: &ScalarValue) -> f64 {
+        if self.row_count == 0 {
+            return 0.0;
+        }
+        if let Some((_, count)) = self
+            .most_common_values
+            .iter()
+            .find(|(mcv, _)| mcv == value)
+        {
+            return *count as f64 / self.row_count as f64;
+        }
+        if self.distinct_count == 0 {
+            return 0.0;
+        }
+        1.0 / self.distinct_count as f64
+    }
+}
@@ -0,0 +1,84 @@
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+/// A dynamically-typed SQL scalar value, with a `None` payload representing SQL NULL.
+#[derive(Debug, Clone)]
+pub enum ScalarValue {
+    Boolean(Option<bool>),
+    Int32(Option<i32>),
+    Int64(Option<i64>),
+    Float64(Option<f64>),
+    Varchar(Option<String>),
+}
+
+impl ScalarValue {
+    pub fn is_null(&self) -> bool {
+        match self {
+            ScalarValue::Boolean(v) => v.is_none(),
+            ScalarValue::Int32(v) => v.is_none(),
+            ScalarValue::Int64(v) => v.is_none(),
+            ScalarValue::Float64(v) => v.is_none(),
+            ScalarValue::Varchar(v) => v.is_none(),
+        }
+    }
+
+    fn discriminant(&self) -> u8 {
+        match self {
+            ScalarValue::Boolean(_) => 0,
+            ScalarValue::Int32(_) => 1,
+            ScalarValue::Int64(_) => 2,
+            ScalarValue::Float64(_) => 3,
+            ScalarValue::Varchar(_) => 4,
+        }
+    }
+}
+
+impl PartialEq for ScalarValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ScalarValue {}
+
+impl PartialOrd for ScalarValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScalarValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        use ScalarValue::*;
+        match (self, other) {
+            (Boolean(a), Boolean(b)) => a.cmp(b),
+            (Int32(a), Int32(b)) => a.cmp(b),
+            (Int64(a), Int64(b)) => a.cmp(b),
+            // f64 has no total order (NaN), so compare bitwise-totally via total_cmp
+            (Float64(a), Float64(b)) => match (a, b) {
+                (Some(x), Some(y)) => x.total_cmp(y),
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Less,
+                (Some(_), None) => Ordering::Greater,
+            },
+            (Varchar(a), Varchar(b)) => a.cmp(b),
+            // differing variants only occur when comparing values from two
+            // differently-typed columns; fall back to a stable discriminant order
+            _ => self.discriminant().cmp(&other.discriminant()),
+        }
+    }
+}
+
+impl Hash for ScalarValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.discriminant().hash(state);
+        match self {
+            ScalarValue::Boolean(v) => v.hash(state),
+            ScalarValue::Int32(v) => v.hash(state),
+            ScalarValue::Int64(v) => v.hash(state),
+            // bitwise hash so equal floats (per total_cmp/eq above) hash equally
+            ScalarValue::Float64(v) => v.map(f64::to_bits).hash(state),
+            ScalarValue::Varchar(v) => v.hash(state),
+        }
+    }
+}
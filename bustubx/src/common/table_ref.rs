@@ -0,0 +1,14 @@
+/// A reference to a table by name, used wherever a plan node needs to name the table
+/// it reads from or writes to without pulling in the full catalog entry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TableReference {
+    pub table: String,
+}
+
+impl TableReference {
+    pub fn new(table: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+        }
+    }
+}
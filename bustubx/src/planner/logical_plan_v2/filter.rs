@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use crate::catalog::ColumnRef;
+use crate::common::ScalarValue;
+
+use super::LogicalPlanV2;
+
+// fallback selectivities used when a predicate can't be checked against column
+// statistics, e.g. the referenced column carries no ColumnStatistics yet
+const DEFAULT_EQ_SELECTIVITY: f64 = 1.0 / 10.0;
+const DEFAULT_RANGE_SELECTIVITY: f64 = 1.0 / 3.0;
+const DEFAULT_UNKNOWN_SELECTIVITY: f64 = 1.0 / 3.0;
+
+#[derive(Debug, Clone)]
+pub struct Filter {
+    pub input: Arc<LogicalPlanV2>,
+    pub predicate: Predicate,
+}
+
+/// A predicate over a single column, small enough to drive selectivity estimation
+/// against `catalog::ColumnStatistics` without a full expression AST.
+#[derive(Debug, Clone)]
+pub enum Predicate {
+    Eq { column: ColumnRef, value: ScalarValue },
+    Range { column: ColumnRef },
+    Unknown,
+}
+
+impl Predicate {
+    /// Estimates the fraction of rows that satisfy this predicate: the referenced
+    /// column's most-common-value frequency (or `1/NDV`) for equality, a default
+    /// fraction for ranges, and a default fraction for anything else. Reads
+    /// statistics straight off the `ColumnRef` the predicate already holds, rather
+    /// than re-resolving the column by name against a schema that may not be the one
+    /// the statistics were collected against.
+    pub fn selectivity(&self) -> f64 {
+        match self {
+            Predicate::Eq { column, value } => column
+                .statistics
+                .as_ref()
+                .map(|stats| stats.equality_selectivity(value))
+                .unwrap_or(DEFAULT_EQ_SELECTIVITY),
+            Predicate::Range { .. } => DEFAULT_RANGE_SELECTIVITY,
+            Predicate::Unknown => DEFAULT_UNKNOWN_SELECTIVITY,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use crate::catalog::{ColumnRef, SchemaRef};
+
+use super::LogicalPlanV2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Full,
+}
+
+#[derive(Debug, Clone)]
+pub struct Join {
+    pub left: Arc<LogicalPlanV2>,
+    pub right: Arc<LogicalPlanV2>,
+    pub join_type: JoinType,
+    pub on: Vec<(ColumnRef, ColumnRef)>,
+    pub schema: SchemaRef,
+}
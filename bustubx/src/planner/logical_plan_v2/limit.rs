@@ -0,0 +1,10 @@
+use std::sync::Arc;
+
+use super::LogicalPlanV2;
+
+#[derive(Debug, Clone)]
+pub struct Limit {
+    pub input: Arc<LogicalPlanV2>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
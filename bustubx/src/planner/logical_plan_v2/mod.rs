@@ -12,16 +12,17 @@ mod util;
 mod values;
 
 use crate::catalog::{Column, DataType, Schema, SchemaRef};
+
 pub use create_index::CreateIndex;
 pub use create_table::CreateTable;
 pub use empty_relation::EmptyRelation;
-pub use filter::Filter;
+pub use filter::{Filter, Predicate};
 pub use insert::Insert;
-pub use join::Join;
+pub use join::{Join, JoinType};
 pub use limit::Limit;
 pub use project::Project;
 pub use sort::{OrderByExpr, Sort};
-pub use table_scan::TableScan;
+pub use table_scan::{SampleSpec, TableScan};
 pub use util::*;
 pub use values::Values;
 
@@ -59,4 +60,64 @@ impl LogicalPlanV2 {
             LogicalPlanV2::EmptyRelation(EmptyRelation { schema, .. }) => schema,
         }
     }
+
+    /// Estimates how many rows this plan node will produce, propagating cardinality
+    /// through the plan tree using per-column statistics (`catalog::ColumnStatistics`)
+    /// where they're reachable: `Filter` scales its input by its predicate's
+    /// selectivity, `Join` scales by `1 / max(NDV)` over its join-key columns, `Limit`
+    /// subtracts its `offset` and then caps at its own `limit`, and `TableScan` reports
+    /// its real row count (or a sample-resolved count when a `SampleSpec` is set).
+    pub fn estimated_row_count(&self) -> usize {
+        match self {
+            LogicalPlanV2::CreateTable(_) => 0,
+            LogicalPlanV2::CreateIndex(_) => 0,
+            LogicalPlanV2::Filter(Filter { input, predicate }) => {
+                let input_rows = input.estimated_row_count();
+                (input_rows as f64 * predicate.selectivity()).round() as usize
+            }
+            LogicalPlanV2::Insert(_) => 0,
+            LogicalPlanV2::Join(Join { left, right, on, .. }) => {
+                let left_rows = left.estimated_row_count();
+                let right_rows = right.estimated_row_count();
+                let max_ndv = on
+                    .iter()
+                    .flat_map(|(l, r)| {
+                        [
+                            l.statistics.as_ref().map(|s| s.distinct_count),
+                            r.statistics.as_ref().map(|s| s.distinct_count),
+                        ]
+                    })
+                    .flatten()
+                    .max()
+                    .unwrap_or(1)
+                    .max(1);
+                ((left_rows as f64 * right_rows as f64) / max_ndv as f64).round() as usize
+            }
+            LogicalPlanV2::Limit(Limit {
+                input,
+                limit,
+                offset,
+            }) => {
+                let input_rows = input.estimated_row_count();
+                let after_offset = input_rows.saturating_sub(offset.unwrap_or(0));
+                match limit {
+                    Some(n) => after_offset.min(*n),
+                    None => after_offset,
+                }
+            }
+            LogicalPlanV2::Project(Project { input, .. }) => input.estimated_row_count(),
+            LogicalPlanV2::TableScan(TableScan {
+                row_count, sample, ..
+            }) => match sample {
+                Some(spec) => spec.resolve(*row_count),
+                None => *row_count,
+            },
+            LogicalPlanV2::Sort(Sort { input, .. }) => input.estimated_row_count(),
+            LogicalPlanV2::Values(Values { .. }) => {
+                // TODO: return the literal row count once that field is reachable here.
+                1
+            }
+            LogicalPlanV2::EmptyRelation(EmptyRelation { .. }) => 0,
+        }
+    }
 }
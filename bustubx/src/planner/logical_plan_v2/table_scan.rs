@@ -0,0 +1,64 @@
+use crate::catalog::SchemaRef;
+use crate::common::TableReference;
+use crate::primer::reservoir_sampler::ReservoirSampler;
+
+/// A `TABLESAMPLE`-style sample spec carried by `TableScan`: either a fixed row count
+/// or a fraction of the table's rows, drawn uniformly via a `ReservoirSampler`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SampleSpec {
+    Rows(usize),
+    Fraction(f64),
+}
+
+impl SampleSpec {
+    /// Resolves this spec to a concrete reservoir capacity given the table's row count.
+    pub fn resolve(&self, table_row_count: usize) -> usize {
+        match self {
+            SampleSpec::Rows(n) => (*n).min(table_row_count),
+            SampleSpec::Fraction(fraction) => {
+                (table_row_count as f64 * fraction.clamp(0.0, 1.0)).round() as usize
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TableScan {
+    pub table_ref: TableReference,
+    pub table_schema: SchemaRef,
+    pub row_count: usize,
+    pub sample: Option<SampleSpec>,
+}
+
+impl TableScan {
+    pub fn new(table_ref: TableReference, table_schema: SchemaRef, row_count: usize) -> Self {
+        Self {
+            table_ref,
+            table_schema,
+            row_count,
+            sample: None,
+        }
+    }
+
+    pub fn with_sample(mut self, sample: SampleSpec) -> Self {
+        self.sample = Some(sample);
+        self
+    }
+
+    /// Draws a uniform random sample of `rows` according to `self.sample`, or collects
+    /// every row unchanged if no sample spec was set. When a spec is set the sample
+    /// keeps at least one row (if any are offered), even if the spec resolves to zero.
+    pub fn sample_rows<T>(&self, rows: impl Iterator<Item = T>) -> Vec<T> {
+        match self.sample {
+            Some(spec) => {
+                let capacity = spec.resolve(self.row_count).max(1);
+                let mut sampler = ReservoirSampler::new(capacity);
+                for row in rows {
+                    sampler.offer(row);
+                }
+                sampler.into_samples()
+            }
+            None => rows.collect(),
+        }
+    }
+}
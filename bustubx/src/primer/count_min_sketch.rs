@@ -0,0 +1,110 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// A Count-Min Sketch for estimating per-value frequencies over a stream in a single
+/// pass, using a `depth x width` matrix of counters and one independent hash seed per
+/// row.
+pub struct CountMinSketch<KeyType: Hash> {
+    width: usize,
+    depth: usize,
+    counts: Vec<Vec<u64>>,
+    seeds: Vec<u64>,
+    _marker: std::marker::PhantomData<KeyType>,
+}
+
+impl<KeyType: Hash> CountMinSketch<KeyType> {
+    /// Sizes a sketch from the desired relative error `epsilon` and failure probability
+    /// `delta`: `width = ceil(e/epsilon)`, `depth = ceil(ln(1/delta))`.
+    pub fn new(epsilon: f64, delta: f64) -> Self {
+        let width = (std::f64::consts::E / epsilon).ceil() as usize;
+        let depth = (1.0_f64 / delta).ln().ceil() as usize;
+        Self::with_dimensions(width.max(1), depth.max(1))
+    }
+
+    /// Builds a sketch with an explicit `width` and `depth`.
+    pub fn with_dimensions(width: usize, depth: usize) -> Self {
+        let seeds = (0..depth as u64)
+            .map(|i| i.wrapping_mul(0x9E3779B97F4A7C15).wrapping_add(1))
+            .collect();
+        Self {
+            width,
+            depth,
+            counts: vec![vec![0; width]; depth],
+            seeds,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Hashes `key` across each row and increments the corresponding counter by `count`.
+    pub fn add(&mut self, key: &KeyType, count: u64) {
+        for row in 0..self.depth {
+            let idx = self.hash(key, row);
+            self.counts[row][idx] += count;
+        }
+    }
+
+    /// Returns the minimum counter across all rows for `key`, a guaranteed overestimate
+    /// of its true frequency bounded by `epsilon * N`.
+    pub fn estimate(&self, key: &KeyType) -> u64 {
+        (0..self.depth)
+            .map(|row| self.counts[row][self.hash(key, row)])
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Merges another equally-shaped sketch into this one by summing counters
+    /// element-wise, so per-partition sketches can be combined.
+    pub fn merge(&mut self, other: &Self) {
+        assert_eq!(
+            (self.width, self.depth),
+            (other.width, other.depth),
+            "cannot merge CountMinSketch instances with different dimensions"
+        );
+        for (row_a, row_b) in self.counts.iter_mut().zip(other.counts.iter()) {
+            for (a, b) in row_a.iter_mut().zip(row_b.iter()) {
+                *a += *b;
+            }
+        }
+    }
+
+    fn hash(&self, key: &KeyType, row: usize) -> usize {
+        let mut hasher = DefaultHasher::new();
+        self.seeds[row].hash(&mut hasher);
+        key.hash(&mut hasher);
+        (hasher.finish() % self.width as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_are_never_below_true_count() {
+        let mut sketch: CountMinSketch<i32> = CountMinSketch::new(0.01, 0.01);
+        for i in 0..1000 {
+            sketch.add(&(i % 10), 1);
+        }
+
+        for key in 0..10 {
+            assert!(sketch.estimate(&key) >= 100);
+        }
+    }
+
+    #[test]
+    fn unseen_key_estimates_zero() {
+        let sketch: CountMinSketch<i32> = CountMinSketch::new(0.01, 0.01);
+        assert_eq!(sketch.estimate(&42), 0);
+    }
+
+    #[test]
+    fn merge_sums_counters() {
+        let mut a: CountMinSketch<i32> = CountMinSketch::with_dimensions(100, 4);
+        let mut b: CountMinSketch<i32> = CountMinSketch::with_dimensions(100, 4);
+        a.add(&1, 5);
+        b.add(&1, 7);
+
+        a.merge(&b);
+        assert!(a.estimate(&1) >= 12);
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+/// Algorithm R reservoir sampling: keeps a uniform random sample of `capacity` items
+/// drawn from a stream of unknown length, seen in a single pass.
+pub struct ReservoirSampler<T> {
+    capacity: usize,
+    reservoir: Vec<T>,
+    seen: usize,
+    rng_state: u64,
+}
+
+impl<T> ReservoirSampler<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            reservoir: Vec::with_capacity(capacity),
+            seen: 0,
+            // no `rand` dependency elsewhere in the crate, so seed a small splitmix64
+            // generator off the std-provided RandomState hasher instead of adding one
+            rng_state: RandomState::new().build_hasher().finish(),
+        }
+    }
+
+    /// Offers the next item from the stream. The first `capacity` items are kept
+    /// outright; after that, the i-th item (0-indexed) replaces a uniformly random
+    /// reservoir slot with probability `capacity / (i + 1)`.
+    pub fn offer(&mut self, item: T) {
+        let i = self.seen;
+        self.seen += 1;
+
+        if i < self.capacity {
+            self.reservoir.push(item);
+        } else {
+            let j = self.gen_range_inclusive(i);
+            if j < self.capacity {
+                self.reservoir[j] = item;
+            }
+        }
+    }
+
+    /// Draws a value uniformly from `0..=bound` using a splitmix64 step on the
+    /// sampler's internal RNG state.
+    fn gen_range_inclusive(&mut self, bound: usize) -> usize {
+        self.rng_state = self.rng_state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+        (z % (bound as u64 + 1)) as usize
+    }
+
+    pub fn samples(&self) -> &[T] {
+        &self.reservoir
+    }
+
+    pub fn into_samples(self) -> Vec<T> {
+        self.reservoir
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_all_items_when_stream_is_smaller_than_capacity() {
+        let mut sampler = ReservoirSampler::new(10);
+        for i in 0..5 {
+            sampler.offer(i);
+        }
+        assert_eq!(sampler.samples().len(), 5);
+    }
+
+    #[test]
+    fn never_exceeds_capacity_for_a_larger_stream() {
+        let mut sampler = ReservoirSampler::new(10);
+        for i in 0..10_000 {
+            sampler.offer(i);
+        }
+        assert_eq!(sampler.samples().len(), 10);
+    }
+}
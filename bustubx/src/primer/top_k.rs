@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::count_min_sketch::CountMinSketch;
+
+/// A streaming Top-K / heavy-hitters sketch: tracks the approximately `k` most
+/// frequent values seen so far, layered on top of a `CountMinSketch` for frequency
+/// estimation. Maintains a bounded map of at most `k` (key, estimated-count) entries.
+pub struct TopK<KeyType: Hash + Eq + Clone> {
+    k: usize,
+    sketch: CountMinSketch<KeyType>,
+    members: HashMap<KeyType, u64>,
+}
+
+impl<KeyType: Hash + Eq + Clone> TopK<KeyType> {
+    /// Tracks the top `k` keys, backed by a CountMinSketch sized from `epsilon`/`delta`.
+    pub fn new(k: usize, epsilon: f64, delta: f64) -> Self {
+        Self {
+            k,
+            sketch: CountMinSketch::new(epsilon, delta),
+            members: HashMap::with_capacity(k),
+        }
+    }
+
+    /// Bumps `key`'s count in the underlying sketch, then inserts or replaces the
+    /// tracked set's smallest entry if `key`'s estimated frequency now exceeds it.
+    pub fn offer(&mut self, key: KeyType) {
+        self.sketch.add(&key, 1);
+        let estimate = self.sketch.estimate(&key);
+
+        if self.members.contains_key(&key) {
+            self.members.insert(key, estimate);
+            return;
+        }
+
+        if self.members.len() < self.k {
+            self.members.insert(key, estimate);
+            return;
+        }
+
+        let smallest = self
+            .members
+            .iter()
+            .min_by_key(|(_, &count)| count)
+            .map(|(key, &count)| (key.clone(), count));
+
+        if let Some((smallest_key, smallest_count)) = smallest {
+            if estimate > smallest_count {
+                self.members.remove(&smallest_key);
+                self.members.insert(key, estimate);
+            }
+        }
+    }
+
+    /// Returns the tracked (key, estimated-count) entries, sorted by count descending.
+    pub fn top_k(&self) -> Vec<(KeyType, u64)> {
+        let mut entries: Vec<_> = self
+            .members
+            .iter()
+            .map(|(key, &count)| (key.clone(), count))
+            .collect();
+        entries.sort_by(|a, b| b.1.cmp(&a.1));
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_the_most_frequent_keys() {
+        let mut top_k: TopK<i32> = TopK::new(3, 0.001, 0.001);
+        for _ in 0..50 {
+            top_k.offer(1);
+        }
+        for _ in 0..30 {
+            top_k.offer(2);
+        }
+        for _ in 0..20 {
+            top_k.offer(3);
+        }
+        for _ in 0..5 {
+            top_k.offer(4);
+        }
+        top_k.offer(5);
+
+        assert_eq!(top_k.top_k(), vec![(1, 50), (2, 30), (3, 20)]);
+    }
+
+    #[test]
+    fn respects_the_requested_capacity() {
+        let mut top_k: TopK<i32> = TopK::new(2, 0.001, 0.001);
+        for key in 0..10 {
+            top_k.offer(key);
+        }
+
+        assert_eq!(top_k.top_k().len(), 2);
+    }
+}